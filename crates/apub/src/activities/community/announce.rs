@@ -0,0 +1,46 @@
+use crate::activities::voting::{undo_vote::UndoVote, vote::Vote};
+use lemmy_apub_lib::{ActivityCommonFields, ActivityHandler};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use serde::{Deserialize, Serialize};
+
+/// Activities which can be sent to a community and are then forwarded to all of its followers
+/// via an `Announce` wrapper.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum AnnouncableActivities {
+  Vote(Vote),
+  UndoVote(UndoVote),
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for AnnouncableActivities {
+  async fn verify(
+    &self,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    match self {
+      AnnouncableActivities::Vote(a) => a.verify(context, request_counter).await,
+      AnnouncableActivities::UndoVote(a) => a.verify(context, request_counter).await,
+    }
+  }
+
+  async fn receive(
+    &self,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    match self {
+      AnnouncableActivities::Vote(a) => a.receive(context, request_counter).await,
+      AnnouncableActivities::UndoVote(a) => a.receive(context, request_counter).await,
+    }
+  }
+
+  fn common(&self) -> &ActivityCommonFields {
+    match self {
+      AnnouncableActivities::Vote(a) => a.common(),
+      AnnouncableActivities::UndoVote(a) => a.common(),
+    }
+  }
+}
@@ -0,0 +1,182 @@
+use crate::{
+  activities::{
+    community::announce::AnnouncableActivities,
+    generate_activity_id,
+    verify_activity,
+    verify_person_in_community,
+    voting::vote::{Vote, VoteType},
+  },
+  activity_queue::send_to_community_new,
+  extensions::context::lemmy_context,
+  fetcher::{
+    objects::get_or_fetch_and_insert_post_or_comment,
+    person::get_or_fetch_and_upsert_person,
+  },
+  ActorType,
+  PostOrComment,
+};
+use anyhow::anyhow;
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{values::PublicUrl, ActivityCommonFields, ActivityHandler};
+use lemmy_db_queries::{Crud, Likeable};
+use lemmy_db_schema::{
+  source::{
+    comment::{Comment, CommentLike},
+    community::Community,
+    person::Person,
+    post::{Post, PostLike},
+  },
+  CommunityId,
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+use strum_macros::ToString;
+use url::Url;
+
+#[derive(Clone, Debug, ToString, Deserialize, Serialize)]
+pub enum UndoType {
+  Undo,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoVote {
+  to: PublicUrl,
+  object: Vote,
+  cc: [Url; 1],
+  #[serde(rename = "type")]
+  kind: UndoType,
+  #[serde(flatten)]
+  common: ActivityCommonFields,
+}
+
+impl UndoVote {
+  /// Federates the retraction of a previously-sent `Vote`. The local vote API handler should
+  /// call this alongside removing the local like row whenever a user clears their vote.
+  pub async fn send(
+    object: &PostOrComment,
+    actor: &Person,
+    community_id: CommunityId,
+    kind: VoteType,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let community = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??;
+
+    let vote_id = generate_activity_id(kind.clone())?;
+    let vote = Vote {
+      to: PublicUrl::Public,
+      object: object.ap_id(),
+      cc: [community.actor_id()],
+      kind,
+      common: ActivityCommonFields {
+        context: lemmy_context(),
+        id: vote_id,
+        actor: actor.actor_id(),
+        unparsed: Default::default(),
+      },
+    };
+
+    let id = generate_activity_id(UndoType::Undo)?;
+    let undo_vote = UndoVote {
+      to: PublicUrl::Public,
+      object: vote,
+      cc: [community.actor_id()],
+      kind: UndoType::Undo,
+      common: ActivityCommonFields {
+        context: lemmy_context(),
+        id: id.clone(),
+        actor: actor.actor_id(),
+        unparsed: Default::default(),
+      },
+    };
+    let activity = AnnouncableActivities::UndoVote(undo_vote);
+    send_to_community_new(activity, &id, actor, &community, vec![], context).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for UndoVote {
+  async fn verify(
+    &self,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_activity(self.common())?;
+    verify_activity(self.object.common())?;
+    verify_person_in_community(&self.common.actor, &self.cc[0], context, request_counter).await?;
+    if actor_mismatch(&self.common.actor, &self.object.common().actor) {
+      return Err(anyhow!("Cannot undo the vote of a different actor").into());
+    }
+    Ok(())
+  }
+
+  async fn receive(
+    &self,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let actor =
+      get_or_fetch_and_upsert_person(&self.common.actor, context, request_counter).await?;
+    let object =
+      get_or_fetch_and_insert_post_or_comment(&self.object.object, context, request_counter)
+        .await?;
+    match object {
+      PostOrComment::Post(p) => undo_vote_post(actor, p.deref(), context).await,
+      PostOrComment::Comment(c) => undo_vote_comment(actor, c.deref(), context).await,
+    }
+  }
+
+  fn common(&self) -> &ActivityCommonFields {
+    &self.common
+  }
+}
+
+pub(in crate::activities::voting) async fn undo_vote_post(
+  actor: Person,
+  post: &Post,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let post_id = post.id;
+  let person_id = actor.id;
+  blocking(context.pool(), move |conn| {
+    PostLike::remove(conn, person_id, post_id)
+  })
+  .await??;
+  Ok(())
+}
+
+pub(in crate::activities::voting) async fn undo_vote_comment(
+  actor: Person,
+  comment: &Comment,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let comment_id = comment.id;
+  let person_id = actor.id;
+  blocking(context.pool(), move |conn| {
+    CommentLike::remove(conn, person_id, comment_id)
+  })
+  .await??;
+  Ok(())
+}
+
+fn actor_mismatch(undo_actor: &Url, vote_actor: &Url) -> bool {
+  undo_actor != vote_actor
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rejects_undo_from_a_different_actor() {
+    let alice = Url::parse("https://example.com/u/alice").unwrap();
+    let bob = Url::parse("https://example.com/u/bob").unwrap();
+    assert!(actor_mismatch(&bob, &alice));
+    assert!(!actor_mismatch(&alice, &alice));
+  }
+}
@@ -20,7 +20,7 @@ use lemmy_api_common::blocking;
 use lemmy_apub_lib::{values::PublicUrl, ActivityCommonFields, ActivityHandler};
 use lemmy_db_queries::Crud;
 use lemmy_db_schema::{
-  source::{community::Community, person::Person},
+  source::{community::Community, person::Person, post::Post},
   CommunityId,
 };
 use lemmy_utils::LemmyError;
@@ -109,6 +109,23 @@ impl ActivityHandler for Vote {
   ) -> Result<(), LemmyError> {
     verify_activity(self.common())?;
     verify_person_in_community(&self.common.actor, &self.cc[0], context, request_counter).await?;
+
+    let object =
+      get_or_fetch_and_insert_post_or_comment(&self.object, context, request_counter).await?;
+    let (creator_id, community_id) = creator_and_community_id(&object, context).await?;
+
+    let creator = blocking(context.pool(), move |conn| Person::read(conn, creator_id)).await??;
+    if is_self_vote(&self.common.actor, &creator.actor_id()) {
+      return Err(anyhow!("Actor cannot vote on their own post or comment").into());
+    }
+
+    if vote_blocked_by_downvote_setting(&self.kind, downvotes_disabled(community_id, context).await?)
+    {
+      // Downvotes are disabled in this community: accept the activity so the sender doesn't
+      // see a federation error, but don't apply it (enforced again in `receive`).
+      return Ok(());
+    }
+
     Ok(())
   }
 
@@ -121,6 +138,13 @@ impl ActivityHandler for Vote {
       get_or_fetch_and_upsert_person(&self.common.actor, context, request_counter).await?;
     let object =
       get_or_fetch_and_insert_post_or_comment(&self.object, context, request_counter).await?;
+
+    let (_, community_id) = creator_and_community_id(&object, context).await?;
+    if vote_blocked_by_downvote_setting(&self.kind, downvotes_disabled(community_id, context).await?)
+    {
+      return Ok(());
+    }
+
     match object {
       PostOrComment::Post(p) => vote_post(&self.kind, actor, p.deref(), context).await,
       PostOrComment::Comment(c) => vote_comment(&self.kind, actor, c.deref(), context).await,
@@ -131,3 +155,55 @@ impl ActivityHandler for Vote {
     &self.common
   }
 }
+
+async fn creator_and_community_id(
+  object: &PostOrComment,
+  context: &LemmyContext,
+) -> Result<(lemmy_db_schema::PersonId, CommunityId), LemmyError> {
+  match object {
+    PostOrComment::Post(p) => Ok((p.creator_id, p.community_id)),
+    PostOrComment::Comment(c) => {
+      let post_id = c.post_id;
+      let post = blocking(context.pool(), move |conn| Post::read(conn, post_id)).await??;
+      Ok((c.creator_id, post.community_id))
+    }
+  }
+}
+
+async fn downvotes_disabled(
+  community_id: CommunityId,
+  context: &LemmyContext,
+) -> Result<bool, LemmyError> {
+  let community =
+    blocking(context.pool(), move |conn| Community::read(conn, community_id)).await??;
+  Ok(!community.enable_downvotes)
+}
+
+fn is_self_vote(voter: &Url, creator_actor_id: &Url) -> bool {
+  voter == creator_actor_id
+}
+
+fn vote_blocked_by_downvote_setting(kind: &VoteType, downvotes_are_disabled: bool) -> bool {
+  matches!(kind, VoteType::Dislike) && downvotes_are_disabled
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn self_vote_is_detected() {
+    let alice = Url::parse("https://example.com/u/alice").unwrap();
+    let bob = Url::parse("https://example.com/u/bob").unwrap();
+    assert!(is_self_vote(&alice, &alice));
+    assert!(!is_self_vote(&alice, &bob));
+  }
+
+  #[test]
+  fn dislike_is_blocked_only_when_downvotes_disabled() {
+    assert!(vote_blocked_by_downvote_setting(&VoteType::Dislike, true));
+    assert!(!vote_blocked_by_downvote_setting(&VoteType::Dislike, false));
+    assert!(!vote_blocked_by_downvote_setting(&VoteType::Like, true));
+    assert!(!vote_blocked_by_downvote_setting(&VoteType::Like, false));
+  }
+}